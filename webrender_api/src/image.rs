@@ -69,6 +69,52 @@ impl ImageFormat {
     }
 }
 
+/// The matrix used to convert a planar YUV sample to RGB in the fragment
+/// shader. These correspond to the standard broadcast color spaces.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum YuvColorSpace {
+    Rec601 = 1,
+    Rec709 = 2,
+    Rec2020 = 3,
+}
+
+/// Whether the YUV samples span the full `0..=255` range (JPEG/"full" range)
+/// or the studio-swing `16..=235` luma / `16..=240` chroma range ("limited").
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum YuvRange {
+    Limited = 0,
+    Full = 1,
+}
+
+/// The plane layout of a planar YUV external image.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum YuvExternalFormat {
+    /// A luma plane (`ImageFormat::R8`) plus an interleaved chroma plane
+    /// (`ImageFormat::RG8`). Only `y_plane` and `u_plane` are used.
+    Nv12,
+    /// Three separate planes (`ImageFormat::R8` each): Y, U and V.
+    I420,
+}
+
+/// A planar YUV external image: each plane is an independent external texture
+/// that the renderer binds separately and samples in the YUV→RGB fragment
+/// shader, avoiding the CPU-side conversion and the extra full-resolution BGRA
+/// allocation. For `Nv12` the interleaved chroma plane lives in `u_plane` and
+/// `v_plane` is unused.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct YuvExternalData {
+    pub format: YuvExternalFormat,
+    pub color_space: YuvColorSpace,
+    pub color_range: YuvRange,
+    pub y_plane: ExternalImageData,
+    pub u_plane: ExternalImageData,
+    pub v_plane: ExternalImageData,
+}
+
 #[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ImageDescriptor {
     pub format: ImageFormat,
@@ -95,13 +141,43 @@ impl ImageDescriptor {
         self.stride
             .unwrap_or(self.width * self.format.bytes_per_pixel())
     }
+
+    /// The byte offset into the source buffer of the top-left pixel of `rect`,
+    /// honoring `offset` and the row `stride`. The dirty-rect upload path uses
+    /// this to find the first changed row when copying a sub-rectangle into an
+    /// existing texture-cache entry.
+    pub fn compute_offset(&self, rect: &DeviceUintRect) -> u32 {
+        self.offset
+            + rect.origin.y * self.compute_stride()
+            + rect.origin.x * self.format.bytes_per_pixel()
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A partial update of an image that already lives in the texture cache.
+///
+/// When `dirty_rect` is `Some` and `descriptor` matches the cached entry, only
+/// that sub-rectangle is re-uploaded (via `glTexSubImage2D`, using
+/// `ImageDescriptor::stride`/`offset` to locate the changed rows) instead of
+/// reallocating and re-uploading the whole image. A `None` dirty rect, or a
+/// descriptor that differs from the cached one, falls back to a full upload.
+///
+/// Only non-blob data is accepted here — `ImageData::Raw` and
+/// `ExternalImageType::ExternalBuffer`; blob images keep their own
+/// `BlobImageRenderer::update` dirty-rect path.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UpdateImage {
+    pub key: ImageKey,
+    pub descriptor: ImageDescriptor,
+    pub data: ImageData,
+    pub dirty_rect: Option<DeviceUintRect>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ImageData {
     Raw(Arc<Vec<u8>>),
     Blob(BlobImageData),
     External(ExternalImageData),
+    YuvExternal(YuvExternalData),
 }
 
 impl ImageData {
@@ -135,13 +211,24 @@ impl ImageData {
                 ExternalImageType::TextureExternalHandle => false,
                 ExternalImageType::ExternalBuffer => true,
             },
+            &ImageData::YuvExternal(ref yuv) => match yuv.y_plane.image_type {
+                ExternalImageType::Texture2DHandle => false,
+                ExternalImageType::Texture2DArrayHandle => false,
+                ExternalImageType::TextureRectHandle => false,
+                ExternalImageType::TextureExternalHandle => false,
+                ExternalImageType::ExternalBuffer => true,
+            },
             &ImageData::Blob(_) => true,
             &ImageData::Raw(_) => true,
         }
     }
 }
 
-pub trait BlobImageResources {
+/// The resources (font templates, images) a blob rasterizer reads while
+/// producing pixels. Because rasterization now runs on a worker pool, the
+/// implementation must be shareable across threads: the render backend clones
+/// a snapshot (cheaply, via `Arc`-shared interior) and hands it to the workers.
+pub trait BlobImageResources: Send + Sync {
     fn get_font_data(&self, key: FontKey) -> &FontTemplate;
     fn get_image(&self, key: ImageKey) -> Option<(&ImageData, &ImageDescriptor)>;
 }
@@ -153,14 +240,25 @@ pub trait BlobImageRenderer: Send {
 
     fn delete(&mut self, key: ImageKey);
 
+    /// Enqueue a rasterization job. This does not block: the job (the
+    /// `services` snapshot together with `key`, `descriptor` and `dirty_rect`)
+    /// is dispatched to the worker pool, and the result is retrieved later via
+    /// `poll` or `resolve`.
     fn request(
         &mut self,
-        services: &BlobImageResources,
+        services: Arc<BlobImageResources>,
         key: BlobImageRequest,
         descriptor: &BlobImageDescriptor,
         dirty_rect: Option<DeviceUintRect>,
     );
 
+    /// Return every request that has finished rasterizing since the last call,
+    /// without blocking. Requests still in flight are left for a later `poll`
+    /// or for `resolve`.
+    fn poll(&mut self) -> Vec<(BlobImageRequest, BlobImageResult)>;
+
+    /// Block until the given request is rasterized, then return its result.
+    /// If a worker already finished it this only reaps the stored result.
     fn resolve(&mut self, key: BlobImageRequest) -> BlobImageResult;
 
     fn delete_font(&mut self, key: FontKey);
@@ -237,6 +335,21 @@ pub struct ExternalImage<'a> {
     pub v0: f32,
     pub u1: f32,
     pub v1: f32,
+    /// A monotonic stamp identifying the current contents of the external
+    /// image. The texture-cache path records the last value seen for a given
+    /// `ExternalImageId`+`channel_index` and only re-uploads the backing buffer
+    /// when it changes, so an application feeding live frames can simply bump
+    /// this in its `lock()` callback and call `render()` again.
+    pub timestamp: u64,
+    /// An opaque GPU sync object (a `GLsync` from
+    /// `glFenceSync(GL_SYNC_GPU_COMMANDS_COMPLETE, 0)`) that the producer
+    /// inserted after finishing its writes to a `NativeTexture` source. When
+    /// present, the renderer calls `glWaitSync` on the compositor context
+    /// before sampling the texture, so the producer's draws are guaranteed to
+    /// have completed. `None` keeps the unsynchronized behavior used by
+    /// software/`RawData` sources. The handler may delete the fence in the
+    /// matching `unlock()` call.
+    pub sync: Option<u64>,
     pub source: ExternalImageSource<'a>,
 }
 